@@ -0,0 +1,50 @@
+//! Picks a free port for the backend sidecar instead of hard-coding 8000,
+//! and persists the choice to a handshake file so restarts stay stable.
+
+use std::io;
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::Mutex;
+
+pub struct BackendPort(pub Mutex<u16>);
+
+#[tauri::command]
+pub fn get_backend_port(state: tauri::State<BackendPort>) -> u16 {
+    *state.0.lock().unwrap()
+}
+
+fn port_is_free(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+fn pick_free_port() -> io::Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    listener.local_addr().map(|addr| addr.port())
+}
+
+fn read_saved_port(handshake_path: &Path) -> Option<u16> {
+    std::fs::read_to_string(handshake_path)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+fn write_saved_port(handshake_path: &Path, port: u16) {
+    let _ = std::fs::write(handshake_path, port.to_string());
+}
+
+/// Resolves the port the backend sidecar should listen on: reuse the port
+/// saved from a previous run if it's still free, otherwise bind an
+/// ephemeral port and persist the new choice for next time.
+pub fn resolve_backend_port(handshake_path: &Path) -> u16 {
+    if let Some(saved) = read_saved_port(handshake_path) {
+        if port_is_free(saved) {
+            return saved;
+        }
+    }
+
+    let port = pick_free_port().unwrap_or(8000);
+    write_saved_port(handshake_path, port);
+    port
+}
@@ -0,0 +1,92 @@
+//! Tracks whether the backend sidecar is reachable yet, so the frontend can
+//! show a loading/error screen instead of firing requests at a dead port.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendStatus {
+    Starting,
+    Ready,
+    Failed,
+}
+
+pub struct BackendState(pub Mutex<BackendStatus>);
+
+impl Default for BackendState {
+    fn default() -> Self {
+        Self(Mutex::new(BackendStatus::Starting))
+    }
+}
+
+#[tauri::command]
+pub fn backend_status(state: tauri::State<BackendState>) -> BackendStatus {
+    *state.0.lock().unwrap()
+}
+
+/// Issues a bare `GET /health HTTP/1.1` over `stream` and reports whether
+/// the response's status line is a 2xx. Written against raw `TcpStream`
+/// rather than an HTTP client crate, since that's the only dependency this
+/// probe needs.
+fn get_health(stream: &mut std::net::TcpStream, port: u16) -> std::io::Result<bool> {
+    use std::io::{BufRead, BufReader, Write};
+
+    stream.write_all(
+        format!("GET /health HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nConnection: close\r\n\r\n")
+            .as_bytes(),
+    )?;
+
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+
+    Ok(status_line
+        .split_whitespace()
+        .nth(1)
+        .is_some_and(|code| code.starts_with('2')))
+}
+
+/// Polls `127.0.0.1:{port}/health` until it answers with a 2xx (or we run
+/// out of retries), updates the managed `BackendState`, and emits
+/// `backend-ready` or `backend-failed` so the webview can react.
+pub fn probe_backend_ready(
+    app: tauri::AppHandle,
+    port: u16,
+    mut log: impl FnMut(&str) + Send + 'static,
+) {
+    std::thread::spawn(move || {
+        use std::net::TcpStream;
+        use std::time::Duration;
+
+        const ATTEMPTS: u32 = 50;
+        const RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+        let addr = format!("127.0.0.1:{port}")
+            .parse()
+            .expect("127.0.0.1:<port> is always a valid socket address");
+
+        let mut ready = false;
+        for _ in 0..ATTEMPTS {
+            if let Ok(mut stream) = TcpStream::connect_timeout(&addr, RETRY_INTERVAL) {
+                let _ = stream.set_read_timeout(Some(RETRY_INTERVAL));
+                if get_health(&mut stream, port).unwrap_or(false) {
+                    ready = true;
+                    break;
+                }
+            }
+            std::thread::sleep(RETRY_INTERVAL);
+        }
+
+        let status = if ready {
+            BackendStatus::Ready
+        } else {
+            BackendStatus::Failed
+        };
+        *app.state::<BackendState>().0.lock().unwrap() = status;
+
+        let event = if ready { "backend-ready" } else { "backend-failed" };
+        let _ = app.emit(event, ready);
+        log(&format!("backend readiness probe: {event}"));
+    });
+}
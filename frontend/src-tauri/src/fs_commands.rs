@@ -0,0 +1,115 @@
+//! Native filesystem IPC commands used by the organizer UI so it can scan
+//! and preview folders without round-tripping every listing through the
+//! backend sidecar's HTTP API.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryMetaData {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub is_directory: bool,
+    pub is_file: bool,
+    pub is_symlink: bool,
+    pub child_count: Option<usize>,
+    pub permissions: String,
+    pub created: Option<u64>,
+    pub modified: Option<u64>,
+    pub accessed: Option<u64>,
+}
+
+fn epoch_seconds(time: std::io::Result<std::time::SystemTime>) -> Option<u64> {
+    time.ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+}
+
+#[cfg(unix)]
+fn permission_string(metadata: &fs::Metadata) -> String {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = metadata.permissions().mode();
+    let triplet = |shift: u32| {
+        let bits = (mode >> shift) & 0o7;
+        format!(
+            "{}{}{}",
+            if bits & 0o4 != 0 { "r" } else { "-" },
+            if bits & 0o2 != 0 { "w" } else { "-" },
+            if bits & 0o1 != 0 { "x" } else { "-" },
+        )
+    };
+    format!("{}{}{}", triplet(6), triplet(3), triplet(0))
+}
+
+#[cfg(not(unix))]
+fn permission_string(metadata: &fs::Metadata) -> String {
+    if metadata.permissions().readonly() {
+        "r--r--r--".to_string()
+    } else {
+        "rw-rw-rw-".to_string()
+    }
+}
+
+fn child_count(path: &Path, metadata: &fs::Metadata) -> Option<usize> {
+    if !metadata.is_dir() {
+        return None;
+    }
+    fs::read_dir(path).ok().map(|entries| entries.count())
+}
+
+fn build_entry(path: PathBuf) -> std::io::Result<EntryMetaData> {
+    let metadata = fs::symlink_metadata(&path)?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+    Ok(EntryMetaData {
+        name,
+        path: path.to_string_lossy().to_string(),
+        size: metadata.len(),
+        is_directory: metadata.is_dir(),
+        is_file: metadata.is_file(),
+        is_symlink: metadata.is_symlink(),
+        child_count: child_count(&path, &metadata),
+        permissions: permission_string(&metadata),
+        created: epoch_seconds(metadata.created()),
+        modified: epoch_seconds(metadata.modified()),
+        accessed: epoch_seconds(metadata.accessed()),
+    })
+}
+
+/// Lists the immediate children of `path` with their metadata.
+///
+/// A single unreadable/racy child (permission denied, deleted mid-scan) is
+/// skipped rather than failing the whole listing — otherwise one bad entry
+/// would blank the entire folder view. Symlinks are not followed, so a
+/// symlink with a missing target is still listed, as a symlink.
+#[tauri::command]
+pub fn list_directory(path: String) -> Result<Vec<EntryMetaData>, String> {
+    let dir = fs::read_dir(&path).map_err(|err| format!("failed to read {path}: {err}"))?;
+    let mut entries = Vec::new();
+    for entry in dir {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                log::warn!("skipping unreadable entry in {path}: {err}");
+                continue;
+            }
+        };
+        match build_entry(entry.path()) {
+            Ok(entry) => entries.push(entry),
+            Err(err) => log::warn!("skipping {:?}: {err}", entry.path()),
+        }
+    }
+    Ok(entries)
+}
+
+/// Returns metadata for a single file or directory.
+#[tauri::command]
+pub fn stat_entry(path: String) -> Result<EntryMetaData, String> {
+    build_entry(PathBuf::from(path)).map_err(|err| err.to_string())
+}
@@ -1,55 +1,248 @@
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+
+mod backend_port;
+mod backend_status;
+mod fs_commands;
+
+use backend_port::BackendPort;
+use backend_status::{BackendState, BackendStatus};
+
+use std::sync::Mutex;
+
+#[cfg(not(debug_assertions))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(debug_assertions))]
+use tauri_plugin_shell::process::CommandChild;
+
+/// Managed state holding the running backend sidecar, if any.
+///
+/// Guarded by a mutex because both the shutdown handler and the
+/// supervisor task need to observe/replace the child.
+#[cfg(not(debug_assertions))]
+struct SidecarChild(Mutex<Option<CommandChild>>);
+
+/// Set by the exit handler before killing the sidecar, so the supervisor
+/// can tell a deliberate shutdown from an unexpected crash and skip the
+/// respawn in the former case.
+#[cfg(not(debug_assertions))]
+struct ShuttingDown(AtomicBool);
+
+#[cfg(not(debug_assertions))]
+const MAX_RESTARTS: u32 = 5;
+
+/// Once a spawned child has stayed up this long, a subsequent crash is
+/// treated as a fresh problem rather than a continuation of earlier ones:
+/// the restart counter (and backoff) reset instead of growing forever.
+#[cfg(not(debug_assertions))]
+const STABILITY_THRESHOLD: std::time::Duration = std::time::Duration::from_secs(60);
+
+fn data_file_path(app: &tauri::AppHandle, filename: &str) -> std::path::PathBuf {
+    let mut path = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| std::path::PathBuf::from("."));
+    path.push(filename);
+    path
+}
+
+/// Installs a global panic hook that appends a structured crash record to
+/// `crash.log` so field crashes can be diagnosed after the fact.
+fn install_panic_hook(crash_log_path: std::path::PathBuf) {
+    std::panic::set_hook(Box::new(move |info| {
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let thread = std::thread::current();
+        let thread_name = thread.name().unwrap_or("<unnamed>");
+        let location = info
+            .location()
+            .map(|l| l.to_string())
+            .unwrap_or_else(|| "<unknown>".to_string());
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<no message>".to_string());
+
+        let record = format!("{timestamp} | {thread_name} | {location} | {message} | {backtrace}");
+
+        let write_result = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&crash_log_path)
+            .and_then(|mut file| {
+                use std::io::Write;
+                writeln!(file, "{record}")
+            });
+
+        if let Err(err) = write_result {
+            // `tauri_plugin_log` is only initialized in debug builds, so
+            // `log::error!` would silently vanish in release here — exactly
+            // the crash-report path this hook exists to cover. Fall back to
+            // stderr, which release builds still have.
+            eprintln!("failed to write crash report to {crash_log_path:?}: {err}");
+        }
+    }));
+}
+
+#[cfg(not(debug_assertions))]
+fn log_line(log_path: &std::path::Path, line: &str) {
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(log_path) {
+        let _ = writeln!(file, "{line}");
+    }
+}
+
+/// Spawns the backend sidecar through the shell plugin, which resolves the
+/// right binary for the current platform/bundle layout, and returns the
+/// event stream (stdout/stderr/termination) alongside the running child.
+///
+/// Requires `file-organizer-backend` to be listed under `bundle.externalBin`
+/// in `tauri.conf.json`; without that entry `sidecar()` errors at runtime.
+#[cfg(not(debug_assertions))]
+fn spawn_sidecar(
+    app: &tauri::AppHandle,
+    port: u16,
+) -> tauri_plugin_shell::Result<(
+    tauri::async_runtime::Receiver<tauri_plugin_shell::process::CommandEvent>,
+    CommandChild,
+)> {
+    use tauri_plugin_shell::ShellExt;
+
+    app.shell()
+        .sidecar("file-organizer-backend")?
+        .env("FILE_ORGANIZER_BACKEND_PORT", port.to_string())
+        .spawn()
+}
+
+/// Supervises the backend sidecar: forwards its stdout/stderr to
+/// `sidecar.log` and the `backend-log` event, and respawns it with
+/// exponential backoff (up to `MAX_RESTARTS` attempts) if it terminates
+/// unexpectedly. Each restart re-resolves the backend port in case the
+/// previously saved one has since been taken by something else.
+#[cfg(not(debug_assertions))]
+async fn supervise_sidecar(
+    app: tauri::AppHandle,
+    handshake_path: std::path::PathBuf,
+    log_path: std::path::PathBuf,
+) {
+    use tauri_plugin_shell::process::CommandEvent;
+
+    let mut restarts = 0u32;
+
+    loop {
+        let port = backend_port::resolve_backend_port(&handshake_path);
+        *app.state::<BackendPort>().0.lock().unwrap() = port;
+        let _ = app.emit("backend-port", port);
+
+        let mut rx = match spawn_sidecar(&app, port) {
+            Ok((rx, child)) => {
+                *app.state::<SidecarChild>().0.lock().unwrap() = Some(child);
+                rx
+            }
+            Err(err) => {
+                log_line(&log_path, &format!("sidecar spawn failed: {err}"));
+                break;
+            }
+        };
+        let spawned_at = std::time::Instant::now();
+
+        let probe_log_path = log_path.clone();
+        backend_status::probe_backend_ready(app.clone(), port, move |line| {
+            log_line(&probe_log_path, line)
+        });
+
+        let mut terminated = false;
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(bytes) | CommandEvent::Stderr(bytes) => {
+                    let line = String::from_utf8_lossy(&bytes).trim_end().to_string();
+                    log_line(&log_path, &format!("[backend] {line}"));
+                    let _ = app.emit("backend-log", &line);
+                }
+                CommandEvent::Terminated(payload) => {
+                    log_line(&log_path, &format!("sidecar terminated: {payload:?}"));
+                    terminated = true;
+                }
+                _ => {}
+            }
+        }
+
+        // The channel closes once the child has terminated and all of its
+        // output has been drained, whether or not we saw an explicit
+        // `Terminated` event first. `tauri_plugin_shell::process::CommandChild`
+        // has no synchronous `wait()` — draining this channel to close *is*
+        // the reap: by the time `recv()` returns `None` the child is gone.
+        let _ = terminated;
+
+        if app.state::<ShuttingDown>().0.load(Ordering::SeqCst) {
+            log_line(&log_path, "sidecar watchdog: shutdown in progress, not respawning");
+            break;
+        }
+
+        if spawned_at.elapsed() >= STABILITY_THRESHOLD {
+            restarts = 0;
+        }
+
+        if restarts >= MAX_RESTARTS {
+            log_line(&log_path, "sidecar watchdog: giving up after max restarts");
+            break;
+        }
+
+        let backoff = std::time::Duration::from_secs(1 << restarts.min(6));
+        log_line(
+            &log_path,
+            &format!("sidecar watchdog: backend died, restart {}/{MAX_RESTARTS} after {backoff:?}", restarts + 1),
+        );
+        tokio::time::sleep(backoff).await;
+        restarts += 1;
+    }
+}
 
 pub fn run() {
-    tauri::Builder::default()
+    let builder = tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .invoke_handler(tauri::generate_handler![
+            fs_commands::list_directory,
+            fs_commands::stat_entry,
+            backend_status::backend_status,
+            backend_port::get_backend_port
+        ])
         .setup(|app| {
-            // Start the bundled backend sidecar so the frontend can call http://127.0.0.1:8000
-            // If the port is already in use, the backend exits gracefully.
+            install_panic_hook(data_file_path(app.handle(), "crash.log"));
+
+            // Start the bundled backend sidecar so the frontend can call
+            // http://127.0.0.1:<port>, where <port> comes from get_backend_port.
+            // If the saved port is taken, a fresh one is picked and persisted.
             #[cfg(not(debug_assertions))]
             {
-                use std::fs::OpenOptions;
-                use std::io::Write;
-                use std::path::PathBuf;
-
-                let mut log_path = app
-                    .path()
-                    .app_data_dir()
-                    .unwrap_or_else(|_| PathBuf::from("."));
-                log_path.push("sidecar.log");
-
-                let mut log = OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .open(&log_path)
-                    .ok();
-
-                let exe_dir = std::env::current_exe()
-                    .ok()
-                    .and_then(|p| p.parent().map(|p| p.to_path_buf()));
-                let sidecar_path = exe_dir
-                    .as_ref()
-                    .map(|dir| dir.join("file-organizer-backend"));
-
-                let result = if let Some(path) = sidecar_path.clone() {
-                    std::process::Command::new(path).spawn().map(|_| ())
-                } else {
-                    Err(std::io::Error::new(
-                        std::io::ErrorKind::NotFound,
-                        "failed to resolve executable dir",
-                    ))
-                };
-
-                if let Some(ref mut file) = log {
-                    let _ = writeln!(
-                        file,
-                        "sidecar spawn: path={:?} result={:?}",
-                        sidecar_path, result
-                    );
-                }
+                let log_path = data_file_path(app.handle(), "sidecar.log");
+                let handshake_path = data_file_path(app.handle(), "backend-port.txt");
+
+                app.manage(SidecarChild(Mutex::new(None)));
+                app.manage(ShuttingDown(AtomicBool::new(false)));
+                app.manage(BackendPort(Mutex::new(backend_port::resolve_backend_port(
+                    &handshake_path,
+                ))));
+                app.manage(BackendState::default());
+
+                tauri::async_runtime::spawn(supervise_sidecar(
+                    app.handle().clone(),
+                    handshake_path,
+                    log_path,
+                ));
             }
             if cfg!(debug_assertions) {
+                // No sidecar is supervised in dev builds; the backend is run
+                // separately on its default port, so there's nothing to wait on.
+                app.manage(BackendPort(Mutex::new(8000)));
+                app.manage(BackendState(Mutex::new(BackendStatus::Ready)));
                 app.handle().plugin(
                     tauri_plugin_log::Builder::default()
                         .level(log::LevelFilter::Info)
@@ -58,6 +251,33 @@ pub fn run() {
             }
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application");
+
+    builder.run(|_app_handle, _event| {
+        #[cfg(not(debug_assertions))]
+        {
+            use tauri::RunEvent;
+            if matches!(_event, RunEvent::ExitRequested { .. } | RunEvent::Exit) {
+                _app_handle
+                    .state::<ShuttingDown>()
+                    .0
+                    .store(true, Ordering::SeqCst);
+
+                // `kill()` only requests termination; the actual reap happens
+                // when `supervise_sidecar`'s `rx.recv()` observes the channel
+                // close on its background task. Setting the flag above before
+                // killing is what stops that drain from respawning — it does
+                // not guarantee the drain itself completes before this
+                // process exits, since the supervisor task isn't awaited
+                // here. In practice the child dies and the channel closes
+                // quickly enough not to matter, but this is best-effort, not
+                // a synchronous wait.
+                let state = _app_handle.state::<SidecarChild>();
+                if let Some(child) = state.0.lock().unwrap().take() {
+                    let _ = child.kill();
+                }
+            }
+        }
+    });
 }